@@ -4,13 +4,19 @@ use std::process::exit;
 
 use crate::report::{Level, ReportKind};
 
+// `.emit()` only terminates the process if the installed backend says a
+// `Fatal` diagnostic should (see `report::emit`), so this no longer exits
+// unconditionally.
+// Call sites that need a value out of this arm (rather than running as a
+// bare statement) still follow it with an explicit `exit(1)`, since there's
+// no sensible value to produce for a parse that was supposed to be fatal.
 macro_rules! error {
-    ($($ident:tt)*) => {
+    ($($ident:tt)*) => {{
         ReportKind::ArgumentParserError
             .title(format!($($ident)*))
-            .note("(Run with \x1b[1m--help\x1b[0m for usage information)");
-        exit(1);
-    };
+            .note("(Run with \x1b[1m--help\x1b[0m for usage information)")
+            .emit();
+    }};
 }
 
 #[derive(Copy, Clone)]
@@ -46,106 +52,641 @@ impl<T: Debug> Debug for Arg<T> {
     }
 }
 
+/// Levenshtein edit distance between two strings, used to find the closest
+/// known flag/verb to an unrecognized one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+/// Finds the closest match to `token` among `candidates`, rejecting matches
+/// that are too far away to be a plausible typo.
+fn did_you_mean<'a>(token: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, edit_distance(token, candidate)))
+        .filter(|(candidate, distance)| *distance <= (candidate.len() / 3).max(1))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Appends a "(did you mean `...`?)" note to an unrecognized-argument
+/// message when a sufficiently close known option or verb exists.
+macro_rules! unrecognized {
+    ($token:expr, $candidates:expr, $message:literal) => {{
+        match did_you_mean($token, $candidates) {
+            Some(suggestion) => {
+                error!(concat!($message, " (did you mean `{}`?)"), $token, suggestion);
+            },
+            None => {
+                error!($message, $token);
+            },
+        }
+    }};
+}
+
+/// Splits a long `--name` argument on its first `=`, so `--output=x.asm`
+/// is seen as the flag `--output` with an inline value of `x.asm`.
+fn split_long(argument: &str) -> (&str, Option<&str>) {
+    match argument.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (argument, None),
+    }
+}
+
+/// Walks a short-option cluster such as `-dfmain.shd` one flag at a time.
+/// `handle` is called with each flag (`-d`, then `-f`, ...) and whatever of
+/// the cluster remains after it; returning `true` tells the walk that the
+/// remainder was consumed as that flag's value, so it stops early instead
+/// of trying to parse the rest as more flags.
+fn walk_cluster(argument: &str, mut handle: impl FnMut(&str, &str) -> bool) {
+    let chars: Vec<char> = argument.chars().skip(1).collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let flag = format!("-{}", chars[i]);
+        let rest: String = chars[i + 1..].iter().collect();
+
+        if handle(&flag, &rest) {
+            return;
+        }
+
+        i += 1;
+    }
+}
+
+/// Resolves the value for an option that takes one: the rest of its short
+/// cluster (`-fmain.shd`), an inline `=value` (`--file=main.shd`), or
+/// failing both, the next whitespace-separated argument.
+fn take_value<N: std::fmt::Display + Copy>(
+    name: N,
+    inline: Option<&str>,
+    arguments: &mut std::vec::IntoIter<String>,
+) -> String {
+    if let Some(value) = inline {
+        if !value.is_empty() {
+            return value.to_string();
+        }
+    }
+
+    arguments.next().unwrap_or_else(|| {
+        error!("{name} expected a value");
+        exit(1);
+    })
+}
+
+/// Expands every `@path` token into the whitespace/newline-separated
+/// tokens of the file at `path`, splicing them into the argument stream in
+/// place so a long invocation can live in a response file.
+fn expand_response_files(args: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+
+    for arg in args {
+        match arg.strip_prefix('@') {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path).unwrap_or_else(|err| {
+                    error!("failed to read response file `{path}`: {err}");
+                    exit(1)
+                });
+                out.extend(contents.split_whitespace().map(String::from));
+            },
+            None => out.push(arg),
+        }
+    }
+
+    out
+}
+
+/// The verb (subcommand) a `sharc` invocation was run with, together with
+/// the options that verb accepts. Each variant parses its own tail of the
+/// argument list once its name has been consumed.
+#[derive(Debug)]
+pub enum Verb {
+    Build(BuildArgs),
+    Check(CheckArgs),
+    Fmt(FmtArgs),
+    Completions(CompletionsArgs),
+}
+
+impl Verb {
+    /// Known verb names, used for dispatch and for "did you mean" style
+    /// error messages.
+    pub const NAMES: [&'static str; 4] = ["build", "check", "fmt", "completions"];
+
+    fn parse(name: &str, args: &mut std::vec::IntoIter<String>) -> Self {
+        match name {
+            "build" => Verb::Build(BuildArgs::parse(args)),
+            "check" => Verb::Check(CheckArgs::parse(args)),
+            "fmt" => Verb::Fmt(FmtArgs::parse(args)),
+            "completions" => Verb::Completions(CompletionsArgs::parse(args)),
+            _ => {
+                unrecognized!(name, &Verb::NAMES, "unrecognized verb `{}`");
+                exit(1)
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BuildArgs {
+    pub output: Arg<&'static str>,
+}
+
+impl BuildArgs {
+    const OPTIONS: [&'static str; 4] = ["-h", "--help", "-o", "--output"];
+    const USAGE: &'static str = "Usage: sharc build [-h] [-o FILE]";
+
+    fn parse(args: &mut std::vec::IntoIter<String>) -> Self {
+        let mut out = Self { output: Arg::new("main.asm") };
+
+        while let Some(argument) = args.next() {
+            if !argument.starts_with('-') {
+                error!("build takes no positional arguments, found `{argument}`");
+            }
+
+            if argument.starts_with("--") {
+                let (name, inline) = split_long(&argument);
+                match name {
+                    "--help" => {
+                        println!("{}", Self::USAGE);
+                        exit(0);
+                    },
+                    "--output" => {
+                        let value = take_value(name, inline, args);
+                        out.output.try_mut(name, Box::leak(value.into_boxed_str()));
+                    },
+                    _ => unrecognized!(name, &Self::OPTIONS, "unrecognized argument {}"),
+                }
+                continue;
+            }
+
+            walk_cluster(&argument, |flag, rest| match flag {
+                "-h" => {
+                    println!("{}", Self::USAGE);
+                    exit(0);
+                },
+                "-o" => {
+                    let value = take_value(flag, Some(rest), args);
+                    out.output.try_mut(flag, Box::leak(value.into_boxed_str()));
+                    true
+                },
+                _ => {
+                    unrecognized!(flag, &Self::OPTIONS, "unrecognized argument {}");
+                    exit(1)
+                },
+            });
+        }
+
+        out
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckArgs;
+
+impl CheckArgs {
+    const OPTIONS: [&'static str; 2] = ["-h", "--help"];
+    const USAGE: &'static str = "Usage: sharc check [-h]";
+
+    fn parse(args: &mut std::vec::IntoIter<String>) -> Self {
+        for argument in args.by_ref() {
+            if !argument.starts_with('-') {
+                error!("check takes no positional arguments, found `{argument}`");
+            }
+
+            if argument.starts_with("--") {
+                match split_long(&argument).0 {
+                    "--help" => {
+                        println!("{}", Self::USAGE);
+                        exit(0);
+                    },
+                    name => unrecognized!(name, &Self::OPTIONS, "unrecognized argument {}"),
+                }
+            }
+            else {
+                walk_cluster(&argument, |flag, _rest| match flag {
+                    "-h" => {
+                        println!("{}", Self::USAGE);
+                        exit(0);
+                    },
+                    _ => {
+                        unrecognized!(flag, &Self::OPTIONS, "unrecognized argument {}");
+                        exit(1)
+                    },
+                });
+            }
+        }
+
+        CheckArgs
+    }
+}
+
+#[derive(Debug)]
+pub struct FmtArgs {
+    pub write: Arg<bool>,
+}
+
+impl FmtArgs {
+    const OPTIONS: [&'static str; 4] = ["-h", "--help", "-w", "--write"];
+    const USAGE: &'static str = "Usage: sharc fmt [-h] [-w]";
+
+    fn parse(args: &mut std::vec::IntoIter<String>) -> Self {
+        let mut out = Self { write: Arg::new(false) };
+
+        for argument in args.by_ref() {
+            if !argument.starts_with('-') {
+                error!("fmt takes no positional arguments, found `{argument}`");
+            }
+
+            if argument.starts_with("--") {
+                match split_long(&argument).0 {
+                    "--help" => {
+                        println!("{}", Self::USAGE);
+                        exit(0);
+                    },
+                    "--write" => out.write.try_mut("--write", true),
+                    name => unrecognized!(name, &Self::OPTIONS, "unrecognized argument {}"),
+                }
+                continue;
+            }
+
+            walk_cluster(&argument, |flag, _rest| {
+                match flag {
+                    "-h" => {
+                        println!("{}", Self::USAGE);
+                        exit(0);
+                    },
+                    "-w" => out.write.try_mut(flag, true),
+                    _ => unrecognized!(flag, &Self::OPTIONS, "unrecognized argument {}"),
+                }
+                false
+            });
+        }
+
+        out
+    }
+}
+
+/// A shell that `sharc completions` knows how to generate a script for.
+#[derive(Debug, Clone, Copy)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    const NAMES: [&'static str; 3] = ["bash", "zsh", "fish"];
+
+    fn parse(name: &str) -> Self {
+        match name {
+            "bash" => Shell::Bash,
+            "zsh" => Shell::Zsh,
+            "fish" => Shell::Fish,
+            _ => {
+                unrecognized!(name, &Self::NAMES, "unrecognized shell `{}`");
+                exit(1)
+            },
+        }
+    }
+}
+
+/// What parsing a global option actually does, once it's been matched by
+/// name. Kept as a plain enum (rather than letting the parser hand-match
+/// flag names) so the parser dispatches off [`GLOBAL_OPTIONS`]; the
+/// shell-completion generator reads the same table, though the bash script
+/// still names `-l`/`--error-level` itself for its value completion.
+#[derive(Debug, Clone, Copy)]
+pub enum OptionAction {
+    Help,
+    Version,
+    Debug,
+    File,
+    ErrorLevel,
+    NoContext,
+}
+
+/// Static description of a global option, shared by the argument parser
+/// (for dispatch and "did you mean" suggestions) and the shell-completion
+/// generator, so the two can never drift out of sync.
+pub struct OptionSpec {
+    pub long:        &'static str,
+    pub short:       Option<&'static str>,
+    pub takes_value: bool,
+    pub values:      &'static [&'static str],
+    pub action:      OptionAction,
+}
+
+const GLOBAL_OPTIONS: &[OptionSpec] = &[
+    OptionSpec { long: "--help", short: Some("-h"), takes_value: false, values: &[], action: OptionAction::Help },
+    OptionSpec {
+        long:        "--version",
+        short:       Some("-V"),
+        takes_value: false,
+        values:      &[],
+        action:      OptionAction::Version,
+    },
+    OptionSpec { long: "--debug", short: Some("-d"), takes_value: false, values: &[], action: OptionAction::Debug },
+    OptionSpec { long: "--file", short: Some("-f"), takes_value: true, values: &[], action: OptionAction::File },
+    OptionSpec {
+        long:        "--error-level",
+        short:       Some("-l"),
+        takes_value: true,
+        values:      &["fatal", "error", "warn", "note", "silent"],
+        action:      OptionAction::ErrorLevel,
+    },
+    OptionSpec {
+        long:        "--no-context",
+        short:       None,
+        takes_value: false,
+        values:      &[],
+        action:      OptionAction::NoContext,
+    },
+];
+
+/// All long and short names in [`GLOBAL_OPTIONS`], flattened for
+/// "did you mean" lookups.
+fn global_option_names() -> Vec<&'static str> {
+    GLOBAL_OPTIONS.iter().flat_map(|option| std::iter::once(option.long).chain(option.short)).collect()
+}
+
+/// Finds the [`OptionSpec`] whose long name matches `name`.
+fn find_option_by_long(name: &str) -> Option<&'static OptionSpec> {
+    GLOBAL_OPTIONS.iter().find(|option| option.long == name)
+}
+
+/// Finds the [`OptionSpec`] whose short name matches `flag`.
+fn find_option_by_short(flag: &str) -> Option<&'static OptionSpec> {
+    GLOBAL_OPTIONS.iter().find(|option| option.short == Some(flag))
+}
+
+/// Renders a bash completion function driven by [`GLOBAL_OPTIONS`] and
+/// [`Verb::NAMES`].
+fn generate_bash() -> String {
+    let flags = global_option_names().join(" ");
+    let verbs = Verb::NAMES.join(" ");
+    let levels = GLOBAL_OPTIONS
+        .iter()
+        .find(|option| option.long == "--error-level")
+        .map_or(String::new(), |option| option.values.join(" "));
+
+    format!(
+        "_sharc() {{
+    local cur prev
+    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"
+    prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"
+
+    case \"$prev\" in
+        -l|--error-level)
+            COMPREPLY=($(compgen -W \"{levels}\" -- \"$cur\"))
+            return
+            ;;
+    esac
+
+    COMPREPLY=($(compgen -W \"{flags} {verbs}\" -- \"$cur\"))
+}}
+complete -F _sharc sharc
+"
+    )
+}
+
+/// Renders a zsh completion function driven by [`GLOBAL_OPTIONS`] and
+/// [`Verb::NAMES`].
+fn generate_zsh() -> String {
+    let mut specs = String::new();
+    for option in GLOBAL_OPTIONS {
+        let names = match option.short {
+            Some(short) => format!("{{{short},{long}}}", long = option.long),
+            None => option.long.to_string(),
+        };
+        if !option.takes_value {
+            specs.push_str(&format!("        '{names}[{long}]' \\\n", long = option.long));
+        }
+        else if option.values.is_empty() {
+            specs.push_str(&format!("        '{names}[{long}]:value:_files' \\\n", long = option.long));
+        }
+        else {
+            let values = option.values.join(" ");
+            specs.push_str(&format!("        '{names}[{long}]:value:({values})' \\\n", long = option.long));
+        }
+    }
+    let verbs = Verb::NAMES.join(" ");
+
+    format!(
+        "#compdef sharc
+
+_sharc() {{
+    _arguments -s \\
+{specs}        '1:verb:({verbs})'
+}}
+
+_sharc
+"
+    )
+}
+
+/// Renders a fish completion script driven by [`GLOBAL_OPTIONS`] and
+/// [`Verb::NAMES`].
+fn generate_fish() -> String {
+    let mut lines = String::new();
+
+    for option in GLOBAL_OPTIONS {
+        let long = option.long.trim_start_matches('-');
+        let short = option.short.map_or(String::new(), |s| format!(" -s {}", s.trim_start_matches('-')));
+
+        if !option.takes_value {
+            lines.push_str(&format!("complete -c sharc -l {long}{short}\n"));
+        }
+        else if option.values.is_empty() {
+            lines.push_str(&format!("complete -c sharc -l {long}{short} -r\n"));
+        }
+        else {
+            let values = option.values.join(" ");
+            lines.push_str(&format!("complete -c sharc -l {long}{short} -xa '{values}'\n"));
+        }
+    }
+
+    for verb in Verb::NAMES {
+        lines.push_str(&format!("complete -c sharc -n '__fish_use_subcommand' -a {verb}\n"));
+    }
+
+    lines
+}
+
+/// Generates the completion script for `shell`, driven by the same option
+/// metadata the parser validates against.
+fn generate_completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => generate_bash(),
+        Shell::Zsh => generate_zsh(),
+        Shell::Fish => generate_fish(),
+    }
+}
+
+#[derive(Debug)]
+pub struct CompletionsArgs {
+    pub shell: Shell,
+}
+
+impl CompletionsArgs {
+    const USAGE: &'static str = "Usage: sharc completions <bash|zsh|fish>";
+
+    fn parse(args: &mut std::vec::IntoIter<String>) -> Self {
+        let shell_name = args.next().unwrap_or_else(|| {
+            error!("completions expected a shell name ({})", Shell::NAMES.join("|"));
+            exit(1)
+        });
+
+        if shell_name == "-h" || shell_name == "--help" {
+            println!("{}", Self::USAGE);
+            exit(0);
+        }
+
+        let shell = Shell::parse(&shell_name);
+
+        if let Some(extra) = args.next() {
+            error!("completions takes a single shell name, found `{extra}`");
+        }
+
+        Self { shell }
+    }
+}
+
 #[derive(Debug)]
 pub struct Args {
     pub file:         Arg<&'static str>,
-    pub output:       Arg<&'static str>,
     pub debug:        Arg<bool>,
     pub code_context: Arg<bool>,
     pub level:        Arg<Level>,
-    pub verbs:        Vec<&'static str>,
+    pub verb:         Option<Verb>,
 }
 
 impl Args {
     pub fn default() -> Self {
         Self {
             file:         Arg::new("main.shd"),
-            output:       Arg::new("main.asm"),
             debug:        Arg::new(false),
             code_context: Arg::new(true),
             level:        Arg::new(Level::Warn),
-            verbs:        Vec::new(),
+            verb:         None,
         }
     }
 
-    fn handle_arg(&mut self, argument: &str, arguments: &mut std::vec::IntoIter<String>) {
-        let args: Vec<String> = if argument.starts_with("--") {
-            vec![argument.into()]
+    fn parse_level(name: &str, level: &str) -> Level {
+        match level {
+            "f" | "fatal" => Level::Fatal,
+            "e" | "error" => Level::Error,
+            "w" | "warn" => Level::Warn,
+            "n" | "note" => Level::Note,
+            "s" | "silent" => Level::Silent,
+            _ => {
+                error!("{name}: invalid level `{level}`");
+                exit(1)
+            },
         }
-        else {
-            argument.chars().skip(1).map(|c| format!("-{c}")).collect()
-        };
-        let args_len = args.len();
-
-        for (i, arg) in args.into_iter().enumerate() {
-            let is_end = i == args_len - 1;
+    }
 
-            macro_rules! is_end {
-                () => {
-                    if !is_end {
-                        error!("{} may only be used at the end of a group", arg);
-                    }
-                };
-            }
-            match arg.as_str() {
-                "-h" => {
-                    println!("{USAGE}");
-                    exit(0);
-                },
-                "--help" => {
+    /// Carries out `action` (looked up from [`GLOBAL_OPTIONS`] by either
+    /// [`handle_long`](Self::handle_long) or
+    /// [`handle_short`](Self::handle_short)), so the two never hand-match
+    /// flag names independently of the table. Returns whether `rest`/the
+    /// inline value was consumed as this option's value, for
+    /// [`walk_cluster`]'s benefit.
+    fn apply_option(
+        &mut self,
+        action: OptionAction,
+        name: &str,
+        inline: Option<&str>,
+        arguments: &mut std::vec::IntoIter<String>,
+        full_help: bool,
+    ) -> bool {
+        match action {
+            OptionAction::Help => {
+                if full_help {
                     println!("{USAGE}\n\n{HELP_MESSAGE}");
-                    exit(0);
-                },
-                "-V" | "--version" => {
-                    println!("sharc {}", env!("CARGO_PKG_VERSION"));
-                    exit(0);
-                },
-                "-d" | "--debug" => self.debug.try_mut(arg, true),
-                "-f" | "--file" => {
-                    is_end!();
-                    let file = arguments.next().unwrap_or_else(|| {
-                        error!("{arg} expected FILE");
-                    });
-
-                    self.file.try_mut(arg, Box::leak(file.into_boxed_str()));
-                },
-                "-o" | "--output" => {
-                    is_end!();
-                    let output = arguments.next().unwrap_or_else(|| {
-                        error!("expected file");
-                    });
+                }
+                else {
+                    println!("{USAGE}");
+                }
+                exit(0);
+            },
+            OptionAction::Version => {
+                println!("sharc {}", env!("CARGO_PKG_VERSION"));
+                exit(0);
+            },
+            OptionAction::Debug => {
+                self.debug.try_mut(name, true);
+                false
+            },
+            OptionAction::File => {
+                let value = take_value(name, inline, arguments);
+                self.file.try_mut(name, Box::leak(value.into_boxed_str()));
+                true
+            },
+            OptionAction::ErrorLevel => {
+                let value = take_value(name, inline, arguments);
+                self.level.try_mut(name, Self::parse_level(name, &value));
+                crate::report::set_level(*self.level);
+                true
+            },
+            OptionAction::NoContext => {
+                self.code_context.try_mut(name, false);
+                crate::report::set_code_context(false);
+                false
+            },
+        }
+    }
 
-                    self.output.try_mut(arg, Box::leak(output.into_boxed_str()));
-                },
-                "-l" | "--error-level" => {
-                    is_end!();
-                    let level = arguments.next().unwrap_or_else(|| {
-                        error!("expected level");
-                    });
-
-                    self.level.try_mut(arg, match level.as_str() {
-                        "f" | "fatal" => Level::Fatal,
-                        "e" | "error" => Level::Error,
-                        "w" | "warn" => Level::Warn,
-                        "n" | "note" => Level::Note,
-                        "s" | "silent" => Level::Silent,
-                        _ => {
-                            error!("invalid level `{level}`");
-                        },
-                    });
-                },
-                "--no-context" => self.code_context.try_mut(arg, false),
+    fn handle_long(&mut self, argument: &str, arguments: &mut std::vec::IntoIter<String>) {
+        let (name, inline) = split_long(argument);
 
-                _ => {
-                    error!("unrecognized argument {arg}");
-                },
-            }
+        match find_option_by_long(name) {
+            Some(option) => {
+                self.apply_option(option.action, name, inline, arguments, true);
+            },
+            None => unrecognized!(name, &global_option_names(), "unrecognized argument {}"),
+        }
+    }
+
+    fn handle_short(&mut self, argument: &str, arguments: &mut std::vec::IntoIter<String>) {
+        walk_cluster(argument, |flag, rest| match find_option_by_short(flag) {
+            Some(option) => self.apply_option(option.action, flag, Some(rest), arguments, false),
+            None => {
+                unrecognized!(flag, &global_option_names(), "unrecognized argument {}");
+                exit(1)
+            },
+        });
+    }
+
+    fn handle_arg(&mut self, argument: &str, arguments: &mut std::vec::IntoIter<String>) {
+        if argument.starts_with("--") {
+            self.handle_long(argument, arguments);
+        }
+        else {
+            self.handle_short(argument, arguments);
         }
     }
 
     pub fn parse(args: Vec<String>) -> Self {
         let mut out = Self::default();
-        let mut args = args.into_iter();
+        let mut args = expand_response_files(args).into_iter();
 
         while let Some(arg) = args.next() {
             if arg.starts_with('-') {
@@ -158,19 +699,21 @@ impl Args {
                 exit(1);
             }
 
-            out.verbs.push(Box::leak(arg.into_boxed_str()));
-        }
+            let verb = Verb::parse(&arg, &mut args);
+            if let Verb::Completions(completions) = &verb {
+                println!("{}", generate_completions(completions.shell));
+                exit(0);
+            }
 
-        // drain remaining args
-        for arg in args.by_ref() {
-            out.verbs.push(Box::leak(arg.into_boxed_str()));
+            out.verb = Some(verb);
+            break;
         }
 
         out
     }
 }
 
-const USAGE: &str = "Usage: sharc [-hVd] [-l LEVEL] [-f FILE] [-o FILE] [VERB...]";
+const USAGE: &str = "Usage: sharc [-hVd] [-l LEVEL] [-f FILE] VERB [VERB-OPTIONS...]";
 const HELP_MESSAGE: &str = "\x1b[1mDESCRIPTION\x1b[0m
     The compiler for the Shard Programming Language.
     Documentation can be found at https://shardlang.org/doc/
@@ -184,10 +727,19 @@ const HELP_MESSAGE: &str = "\x1b[1mDESCRIPTION\x1b[0m
         (default: warn)
     -f, --file FILE             File to compile
         (default: main.shd)
-    -o, --output FILE           File to write to
-        (default: main.asm)
 
-        --no-context            Disable code context";
+        --no-context            Disable code context
+
+    Options taking a value also accept `--opt=value`, `-oVALUE`, and
+    `@path` to splice in the whitespace-separated contents of a file.
+
+\x1b[1mVERBS\x1b[0m
+    build                       Compile FILE to an assembly output
+    check                       Type-check FILE without producing output
+    fmt                         Reformat FILE in place
+    completions SHELL           Print a completion script [bash|zsh|fish]
+
+    Run `sharc VERB -h` for a verb's own options.";
 const SHARK_ASCII: &str = r#"                                 ,-
                                ,'::|
                               /::::|
@@ -202,3 +754,75 @@ const SHARK_ASCII: &str = r#"                                 ,-
                   \. /    `-._   `.""-----.,-..::(--"".\""`.  `:\
                    `P         `-._ \          `-:\          `. `:\
                                    ""            "            `-._)"#;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::report::Backend;
+
+    /// A `Backend` that records diagnostics instead of printing them, and
+    /// never terminates the process for `Fatal` ones, so tests can exercise
+    /// error paths without killing the test runner.
+    struct BufferBackend {
+        messages: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Backend for BufferBackend {
+        fn emit(&mut self, _level: Level, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+
+        fn is_fatal_terminal(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn unrecognized_global_flag_reports_without_exiting() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        crate::report::set_backend(Box::new(BufferBackend { messages: messages.clone() }));
+        crate::report::set_level(Level::Warn);
+
+        let args = Args::parse(vec!["--bogus".to_string()]);
+
+        assert!(args.verb.is_none());
+        assert!(messages.lock().unwrap().iter().any(|message| message.contains("unrecognized argument")));
+    }
+
+    /// A `Backend` that discards messages but, via the trait default,
+    /// still terminates the process on a `Fatal` diagnostic, same as
+    /// `ConsoleBackend`.
+    struct TerminatingBackend;
+
+    impl Backend for TerminatingBackend {
+        fn emit(&mut self, _level: Level, _message: &str) {}
+    }
+
+    /// `--error-level silent` filters which diagnostics get *printed*; it
+    /// must not also suppress termination on a `Fatal` usage error, or
+    /// `sharc --error-level silent --bogus build` would silently proceed
+    /// as if `--bogus` had never been passed. Exercised out-of-process
+    /// since a passing case here really does call `exit(1)`.
+    #[test]
+    fn unrecognized_global_flag_still_exits_under_silent_filter() {
+        const MARKER: &str = "SHARC_TEST_SILENT_UNRECOGNIZED_EXIT";
+
+        if std::env::var_os(MARKER).is_some() {
+            crate::report::set_backend(Box::new(TerminatingBackend));
+            crate::report::set_level(Level::Silent);
+            Args::parse(vec!["--bogus".to_string()]);
+            return;
+        }
+
+        let exe = std::env::current_exe().unwrap();
+        let output = std::process::Command::new(exe)
+            .args(["--exact", "args::tests::unrecognized_global_flag_still_exits_under_silent_filter"])
+            .env(MARKER, "1")
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "expected --error-level silent to still terminate on a Fatal usage error");
+    }
+}
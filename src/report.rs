@@ -0,0 +1,308 @@
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::process::exit;
+use std::sync::{Mutex, OnceLock};
+
+/// Severity of a diagnostic, also used as the `--error-level` filter: a
+/// diagnostic is shown only when it is at least as severe as the
+/// configured level. Declaration order is severity order, from most to
+/// least severe, with `Silent` suppressing everything.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Fatal,
+    Error,
+    Warn,
+    Note,
+    Silent,
+}
+
+/// The kind of diagnostic being reported. Each kind has a fixed [`Level`]
+/// and label, so callers don't have to repeat that decision at every call
+/// site.
+#[derive(Debug, Copy, Clone)]
+pub enum ReportKind {
+    ArgumentParserError,
+}
+
+impl ReportKind {
+    fn level(self) -> Level {
+        match self {
+            ReportKind::ArgumentParserError => Level::Fatal,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ReportKind::ArgumentParserError => "error",
+        }
+    }
+
+    /// Starts building a [`Report`] of this kind with the given title.
+    pub fn title(self, title: String) -> Report {
+        Report { kind: self, title, notes: Vec::new(), snippet: None }
+    }
+}
+
+/// A single labeled range into a [`Snippet`]'s source, in byte offsets.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub range: Range<usize>,
+    pub text:  Option<String>,
+}
+
+impl Label {
+    pub fn new(range: Range<usize>) -> Self {
+        Self { range, text: None }
+    }
+
+    pub fn with_text(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self { range, text: Some(text.into()) }
+    }
+}
+
+/// A source snippet attached to a [`Report`]: the primary span is
+/// underlined, secondary spans are called out below it.
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    pub file:      String,
+    pub source:    String,
+    pub primary:   Label,
+    pub secondary: Vec<Label>,
+}
+
+/// A diagnostic under construction: a title, zero or more supporting
+/// notes, and an optional source snippet. Nothing is emitted until
+/// [`Report::emit`] is called.
+pub struct Report {
+    kind:     ReportKind,
+    title:    String,
+    notes:    Vec<String>,
+    snippet:  Option<Snippet>,
+}
+
+impl Report {
+    /// Appends a supporting note, to be rendered below the title.
+    pub fn note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// Attaches a source snippet, rendered as a caret diagnostic (or a
+    /// terse one-liner when `--no-context` is in effect).
+    pub fn snippet(mut self, snippet: Snippet) -> Self {
+        self.snippet = Some(snippet);
+        self
+    }
+
+    /// Sends this report through the global diagnostic sink, honoring the
+    /// configured `--error-level` filter. `Fatal` reports then terminate the
+    /// process, whether or not the filter let them through, unless the
+    /// backend opts out via [`Backend::is_fatal_terminal`].
+    pub fn emit(self) {
+        let mut message = format!("{}: {}", self.kind.label(), self.title);
+        for note in &self.notes {
+            message.push('\n');
+            message.push_str(note);
+        }
+
+        if let Some(snippet) = &self.snippet {
+            message.push('\n');
+            message.push_str(&if code_context_enabled() {
+                render_snippet(snippet, color_enabled())
+            }
+            else {
+                render_snippet_terse(snippet)
+            });
+        }
+
+        emit(self.kind.level(), &message);
+    }
+}
+
+/// Renders `snippet` as a terse one-liner: just the file and the primary
+/// span's starting position.
+fn render_snippet_terse(snippet: &Snippet) -> String {
+    let (line, col) = line_col(&snippet.source, snippet.primary.range.start);
+    format!("--> {}:{line}:{col}", snippet.file)
+}
+
+/// Renders `snippet` in the `annotate-snippets` style: a gutter of line
+/// numbers, the surrounding source, an underline under the primary span
+/// (using connecting `|` rails down the left margin for spans that cover
+/// more than one line), and any secondary labeled annotations below.
+fn render_snippet(snippet: &Snippet, color: bool) -> String {
+    let lines: Vec<&str> = snippet.source.lines().collect();
+    let (start_line, start_col) = line_col(&snippet.source, snippet.primary.range.start);
+    let (end_line, end_col) = line_col(&snippet.source, snippet.primary.range.end.max(snippet.primary.range.start));
+    let gutter_width = end_line.to_string().len();
+
+    let underline = |text: &str| if color { format!("\x1b[1;31m{text}\x1b[0m") } else { text.to_string() };
+    let location = |text: &str| if color { format!("\x1b[1;34m{text}\x1b[0m") } else { text.to_string() };
+
+    let gutter = |content: &str| format!("{content:>gutter_width$} |");
+    let mut out = String::new();
+
+    out.push_str(&format!("{:gutter_width$} {} {}:{start_line}:{start_col}\n", "", location("-->"), snippet.file));
+    out.push_str(&format!("{}\n", gutter("")));
+
+    if start_line == end_line {
+        let text = lines.get(start_line - 1).copied().unwrap_or("");
+        out.push_str(&format!("{} {text}\n", gutter(&start_line.to_string())));
+
+        let lead = " ".repeat(start_col.saturating_sub(1));
+        let carets = underline(&"^".repeat(end_col.saturating_sub(start_col).max(1)));
+        out.push_str(&format!("{} {lead}{carets}\n", gutter("")));
+    }
+    else {
+        for line_no in start_line..=end_line {
+            let text = lines.get(line_no - 1).copied().unwrap_or("");
+            let rail = if line_no == start_line { " " } else { "|" };
+            out.push_str(&format!("{} {rail}{text}\n", gutter(&line_no.to_string())));
+
+            if line_no == start_line {
+                let lead = " ".repeat(start_col.saturating_sub(1));
+                let width = text.chars().count().saturating_sub(start_col.saturating_sub(1)).max(1);
+                let carets = underline(&"^".repeat(width));
+                out.push_str(&format!("{} {rail}{lead}{carets}\n", gutter("")));
+            }
+            else if line_no == end_line {
+                let carets = underline(&"^".repeat(end_col.saturating_sub(1).max(1)));
+                out.push_str(&format!("{} {rail}{carets}\n", gutter("")));
+            }
+            else {
+                let carets = underline(&"^".repeat(text.chars().count().max(1)));
+                out.push_str(&format!("{} {rail}{carets}\n", gutter("")));
+            }
+        }
+    }
+
+    for label in &snippet.secondary {
+        let Some(text) = &label.text else { continue };
+        let (line, col) = line_col(&snippet.source, label.range.start);
+        out.push_str(&format!("{} {}:{line}:{col}: {text}\n", gutter(""), snippet.file));
+    }
+
+    out
+}
+
+/// Converts a byte offset into `source` to a 1-indexed `(line, column)`.
+fn line_col(source: &str, byte: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut col = 1;
+
+    for (i, ch) in source.char_indices() {
+        if i >= byte {
+            break;
+        }
+
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        }
+        else {
+            col += 1;
+        }
+    }
+
+    (line, col)
+}
+
+/// A diagnostic output backend. The default prints to stderr; tests (or
+/// embedders) can install their own via [`set_backend`] to capture
+/// diagnostics instead of printing them.
+pub trait Backend: Send {
+    fn emit(&mut self, level: Level, message: &str);
+
+    /// Whether a `Fatal` diagnostic that reached this backend should
+    /// terminate the process. Defaults to `true`, matching `ConsoleBackend`;
+    /// test backends can override this to keep the process (and the test
+    /// runner) alive.
+    fn is_fatal_terminal(&self) -> bool {
+        true
+    }
+}
+
+/// The default backend: stderr output, colored according to the global
+/// color decision (see [`set_color`]).
+pub struct ConsoleBackend;
+
+impl Backend for ConsoleBackend {
+    fn emit(&mut self, level: Level, message: &str) {
+        let label = match level {
+            Level::Fatal | Level::Error => "error",
+            Level::Warn => "warning",
+            Level::Note => "note",
+            Level::Silent => return,
+        };
+
+        if color_enabled() {
+            eprintln!("\x1b[1m{label}\x1b[0m: {message}");
+        }
+        else {
+            eprintln!("{label}: {message}");
+        }
+    }
+}
+
+static FILTER: Mutex<Level> = Mutex::new(Level::Warn);
+static CODE_CONTEXT: Mutex<bool> = Mutex::new(true);
+static SINK: OnceLock<Mutex<Box<dyn Backend>>> = OnceLock::new();
+static COLOR: OnceLock<Mutex<bool>> = OnceLock::new();
+
+fn sink() -> &'static Mutex<Box<dyn Backend>> {
+    SINK.get_or_init(|| Mutex::new(Box::new(ConsoleBackend)))
+}
+
+fn color_cell() -> &'static Mutex<bool> {
+    COLOR.get_or_init(|| Mutex::new(std::io::stderr().is_terminal()))
+}
+
+fn color_enabled() -> bool {
+    *color_cell().lock().unwrap()
+}
+
+fn code_context_enabled() -> bool {
+    *CODE_CONTEXT.lock().unwrap()
+}
+
+/// Sets the `--error-level` filter: diagnostics less severe than this are
+/// dropped before reaching the backend.
+pub fn set_level(level: Level) {
+    *FILTER.lock().unwrap() = level;
+}
+
+/// Sets whether snippet diagnostics render full source context
+/// (`--no-context` clears this).
+pub fn set_code_context(enabled: bool) {
+    *CODE_CONTEXT.lock().unwrap() = enabled;
+}
+
+/// Overrides the color/no-color decision (by default, whether stderr is a
+/// terminal).
+pub fn set_color(enabled: bool) {
+    *color_cell().lock().unwrap() = enabled;
+}
+
+/// Swaps the diagnostic backend, e.g. to redirect output into a buffer.
+pub fn set_backend(backend: Box<dyn Backend>) {
+    *sink().lock().unwrap() = backend;
+}
+
+/// Sends `message` through the backend, honoring the `--error-level`
+/// filter. `--error-level` only controls whether the message text is
+/// printed: it's documented as a severity filter for reported source
+/// issues, not a way to suppress termination on a `Fatal` (i.e. usage
+/// error) diagnostic, so whether the process exits depends solely on
+/// [`Backend::is_fatal_terminal`], never on the filter.
+fn emit(level: Level, message: &str) {
+    let filter = *FILTER.lock().unwrap();
+    let mut sink = sink().lock().unwrap();
+
+    if filter != Level::Silent && level <= filter {
+        sink.emit(level, message);
+    }
+
+    if level == Level::Fatal && sink.is_fatal_terminal() {
+        exit(1);
+    }
+}